@@ -0,0 +1,96 @@
+use std::fmt;
+
+/// Error type threaded through every parse/translate path in this crate.
+/// Replaces the old blanket `io::Error::new(ErrorKind::Other, ...)`, which
+/// lost which file and which field failed.
+#[derive(Debug)]
+pub enum QchemError {
+    /// A QM output file has no recognizable energy line.
+    MissingEnergyLine { file: String },
+    /// A fixed-size block of values had the wrong count.
+    CountMismatch {
+        expected: usize,
+        got: usize,
+        file: String,
+    },
+    /// A single field failed to parse.
+    Parse { file: String, field: String },
+    /// Underlying I/O failure.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for QchemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QchemError::MissingEnergyLine { file } => {
+                write!(f, "{}: no energy line found", file)
+            }
+            QchemError::CountMismatch {
+                expected,
+                got,
+                file,
+            } => write!(f, "{}: expected {} values, got {}", file, expected, got),
+            QchemError::Parse { file, field } => {
+                write!(f, "{}: failed to parse {}", file, field)
+            }
+            QchemError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for QchemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            QchemError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for QchemError {
+    fn from(e: std::io::Error) -> Self {
+        QchemError::Io(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, QchemError>;
+
+#[cfg(test)]
+mod tests {
+    use super::QchemError;
+    use std::error::Error;
+
+    #[test]
+    fn count_mismatch_names_file_and_counts() {
+        let e = QchemError::CountMismatch {
+            expected: 21,
+            got: 3,
+            file: "hessian.dat".to_string(),
+        };
+        assert_eq!(e.to_string(), "hessian.dat: expected 21 values, got 3");
+    }
+
+    #[test]
+    fn missing_energy_line_names_file() {
+        let e = QchemError::MissingEnergyLine {
+            file: "qchem.out".to_string(),
+        };
+        assert_eq!(e.to_string(), "qchem.out: no energy line found");
+    }
+
+    #[test]
+    fn parse_names_file_and_field() {
+        let e = QchemError::Parse {
+            file: "qchem.out".to_string(),
+            field: "dipole moment".to_string(),
+        };
+        assert_eq!(e.to_string(), "qchem.out: failed to parse dipole moment");
+    }
+
+    #[test]
+    fn io_error_exposes_source() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let e: QchemError = io_err.into();
+        assert!(e.source().is_some());
+    }
+}
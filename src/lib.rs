@@ -1,39 +1,26 @@
 use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fs::{read_to_string, File};
-use std::io::{Error, ErrorKind, Result, Write};
+use std::io::Write;
 use std::path::Path;
 use std::str::FromStr;
 
-const CONV_FACTOR: f64 = 4.46552493159e-4;
-
-fn parse_energy(qchem_out: &str) -> Result<f64> {
-    let eline = qchem_out
-        .lines()
-        .filter(|x| x.starts_with(" The QM part of the energy is"))
-        .next();
-    // now an Option(str)
-    let out = match eline {
-        Some(val) => Ok(val),
-        None => Err(Error::new(
-            ErrorKind::Other,
-            "no energy line found in output file",
-        )),
-    };
-
-    match out {
-        Ok(val) => val
-            .strip_prefix(" The QM part of the energy is")
-            .unwrap()
-            .trim()
-            .parse::<f64>()
-            .map_err(|_| Error::new(ErrorKind::Other, "failed to parse floats")),
-        Err(e) => Err(e),
-    }
-}
-
-fn parse_nums_from_str<T: FromStr>(n: u16, data: String) -> Result<Vec<T>> {
-    // Parse a vector of floats from a file.
+mod error;
+mod parser;
+mod qchem;
+mod qm_package;
+
+pub use error::{QchemError, Result};
+pub use qchem::QChem;
+pub use qm_package::QmPackage;
+
+pub(crate) fn parse_nums_from_str<T: FromStr>(
+    n: u16,
+    data: String,
+    file: &str,
+    field: &str,
+) -> Result<Vec<T>> {
+    // Parse a vector of values from a file.
     let nums: std::result::Result<Vec<_>, _> =
         data.split_whitespace().map(|x| x.parse::<T>()).collect();
 
@@ -42,58 +29,88 @@ fn parse_nums_from_str<T: FromStr>(n: u16, data: String) -> Result<Vec<T>> {
             if i.len() == n.into() {
                 Ok(i)
             } else {
-                Err(Error::new(
-                    ErrorKind::Other,
-                    format!("expected {} values, got {}", n, i.len()),
-                ))
+                Err(QchemError::CountMismatch {
+                    expected: n.into(),
+                    got: i.len(),
+                    file: file.to_string(),
+                })
             }
         }
-        Err(_) => Err(Error::new(ErrorKind::Other, "failed to parse values")),
+        Err(_) => Err(QchemError::Parse {
+            file: file.to_string(),
+            field: field.to_string(),
+        }),
     }
 }
 
-pub fn qchem_translate_to_gaussian(
+/// Drive `qm` over `calc` and write the result in the format Gaussian's
+/// `external` interface expects to `gaussian_out`, reading the backend's raw
+/// output from `rundir`. Generic over [`QmPackage`] so the engine behind the
+/// Gaussian interface (Q-Chem, ORCA, NWChem, ...) is a caller's choice.
+pub fn translate_to_gaussian(
     gaussian_out: &str,
     calc: &Calculation,
-    qchem_loc: &Path,
-    qchem_out: &Path,
+    qm: &dyn QmPackage,
+    rundir: &Path,
 ) -> Result<()> {
     let mut outfile = File::create(gaussian_out)?;
     let natoms: u16 = calc.natoms.try_into().unwrap();
     let nder = calc.nder;
 
     // energy
-    let energy = parse_energy(&read_to_string(qchem_out)?)?;
+    let energy = qm.parse_energy(rundir)?;
     outfile.write(format!("{:+20.12}", energy).as_bytes())?;
 
     // dipole
-    outfile.write(format!("{:+20.12}{:+20.12}{:+20.12}\n", 0.0, 0.0, 0.0).as_bytes())?;
+    let dipole = qm.parse_dipole(rundir)?;
+    outfile.write(
+        format!(
+            "{:+20.12}{:+20.12}{:+20.12}\n",
+            dipole[0], dipole[1], dipole[2]
+        )
+        .as_bytes(),
+    )?;
 
     // derivatives
     if nder > 0 {
-        let mut data = parse_nums_from_str::<f64>(
-            3 * natoms,
-            read_to_string(Path::new(&qchem_loc).join("efield.dat"))?,
-        )?;
-        for _ in 0..natoms {
-            for el in data.drain(..3) {
+        let gradient = qm.parse_gradient(rundir, calc.natoms)?;
+        for xyz in gradient {
+            for el in xyz {
+                outfile.write(format!("{:+20.12}", el).as_bytes())?;
+            }
+            outfile.write("\n".as_bytes())?;
+        }
+
+        // MM point-charge gradient, written right after the atomic block so
+        // ONIOM/QM-MM optimizations get the forces on their charges back.
+        let pc_gradient =
+            qm.parse_point_charge_gradient(rundir, calc.point_charges.len())?;
+        for xyz in pc_gradient {
+            for el in xyz {
                 outfile.write(format!("{:+20.12}", el).as_bytes())?;
             }
             outfile.write("\n".as_bytes())?;
         }
-        // polarizability + dip derivative (6 + 9 * Natoms)
-        for _ in 0..(2 + 3 * natoms) {
+
+        // polarizability (no QM source for this yet, so still zeros)
+        for _ in 0..2 {
             outfile.write(format!("{:+20.12}{:+20.12}{:+20.12}\n", 0.0, 0.0, 0.0).as_bytes())?;
         }
+
+        // dipole derivative (9 * Natoms), read from the QM output when
+        // requested; falls back to zeros when the backend didn't compute it.
+        let dipole_derivative = qm.parse_dipole_derivative(rundir, calc.natoms)?;
+        for xyz in dipole_derivative {
+            outfile.write(
+                format!("{:+20.12}{:+20.12}{:+20.12}\n", xyz[0], xyz[1], xyz[2]).as_bytes(),
+            )?;
+        }
     }
 
     // hessian
     if nder > 1 {
-        let n_hessian = (3 * natoms) * (3 * natoms + 1) / 2;
-        let data = parse_nums_from_str::<f64>(
-            n_hessian,
-            read_to_string(Path::new(&qchem_loc).join("hessian.dat"))?,
-        )?;
+        let data = qm.parse_hessian(rundir, calc.natoms)?;
+        let conv_factor = qm.unit_conversion();
 
         // Maybe I should have used fortran. Some annoying indexing going down
         // in the next bit. Don't touch! it works.
@@ -104,8 +121,8 @@ pub fn qchem_translate_to_gaussian(
         let mut hess = HashMap::new();
         for i in 0..(3 * natoms) {
             for j in i..(3 * natoms) {
-                hess.insert((i, j), data[k] * CONV_FACTOR);
-                hess.insert((j, i), data[k] * CONV_FACTOR);
+                hess.insert((i, j), data[k] * conv_factor);
+                hess.insert((j, i), data[k] * conv_factor);
                 k += 1;
             }
         }
@@ -130,6 +147,25 @@ pub fn qchem_translate_to_gaussian(
         }
         outfile.write("\n".as_bytes())?;
     }
+
+    // Atomic population charges aren't part of Gaussian's `external` layer
+    // format; QM/MM embedding schemes that re-derive their point charges from
+    // the QM density need them, written alongside gamout. A plain
+    // energy/gradient run has no embedded point charges to re-derive, so a QM
+    // output that doesn't print a charges block shouldn't abort the
+    // translation Gaussian is waiting on — only fail when the job actually
+    // has point charges to feed back.
+    match qm.parse_charges(rundir, calc.natoms) {
+        Ok(charges) => {
+            let mut charges_file = File::create(format!("{}.charges", gaussian_out))?;
+            for charge in charges {
+                charges_file.write(format!("{:+20.12}\n", charge).as_bytes())?;
+            }
+        }
+        Err(_) if calc.point_charges.is_empty() => {}
+        Err(e) => return Err(e),
+    }
+
     Ok(())
 }
 
@@ -141,6 +177,9 @@ pub struct Calculation {
     pub spin: i8,
     pub z: Vec<u8>,
     pub coords: Vec<[f64; 3]>,
+    /// External MM point charges (position, charge) appended by Gaussian
+    /// after the atom block in a QM/MM `external` run.
+    pub point_charges: Vec<([f64; 3], f64)>,
 }
 
 impl Calculation {
@@ -158,40 +197,18 @@ impl Calculation {
 
 pub fn parse_gau_ein(infile: &str) -> Result<Calculation> {
     let gaussfile = read_to_string(infile)?;
-    let mut gauss = gaussfile.lines();
-    if let Some(header) = gauss.next() {
-        // Parse
-        let entries = parse_nums_from_str::<i8>(4, header.to_string())?;
-        let natoms: usize = entries[0].try_into().unwrap();
-        let nder: usize = entries[1].try_into().unwrap();
-        let charge: i8 = entries[2];
-        let spin: i8 = entries[3];
-        let mut coords = Vec::new();
-        let mut zvals = Vec::<u8>::new();
-
-        for _ in 0..natoms {
-            if let Some(line) = gauss.next() {
-                let (start, end) = line.split_at(11);
-                let atom = parse_nums_from_str::<u8>(1, start.to_string())?[0];
-                let vals = parse_nums_from_str::<f64>(4, end.to_string())?;
-                coords.push([vals[0], vals[1], vals[2]]);
-                zvals.push(atom);
-            } else {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "Gaussian input file is truncated",
-                ));
-            }
-        }
-        Ok(Calculation {
-            natoms: natoms,
-            nder: nder,
-            charge: charge,
-            spin: spin,
-            z: zvals,
-            coords: coords,
-        })
-    } else {
-        Err(Error::new(ErrorKind::Other, "Gaussian input file is empty"))
+    match parser::ein_file(&gaussfile) {
+        Ok((remainder, calc)) if remainder.trim().is_empty() => Ok(calc),
+        Ok((remainder, _)) => Err(QchemError::Parse {
+            file: infile.to_string(),
+            field: format!(
+                "trailing point-charge block ({} unparsed bytes left over)",
+                remainder.trim().len()
+            ),
+        }),
+        Err(e) => Err(QchemError::Parse {
+            file: infile.to_string(),
+            field: format!("EIn header/atom block ({})", e),
+        }),
     }
 }
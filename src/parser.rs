@@ -0,0 +1,118 @@
+//! `nom`-based parser for Gaussian's `external` interface input file (EIn),
+//! replacing fixed-column string slicing so stray whitespace in the atom
+//! block no longer breaks parsing silently.
+
+use nom::character::complete::{i8 as nom_i8, i64 as nom_i64, multispace0, multispace1};
+use nom::multi::{many0, many_m_n};
+use nom::number::complete::double as nom_double;
+use nom::sequence::preceded;
+use nom::IResult;
+
+use crate::Calculation;
+
+/// `natoms nder charge spin`, whitespace-separated.
+fn header(input: &str) -> IResult<&str, (usize, usize, i8, i8)> {
+    let (input, _) = multispace0(input)?;
+    let (input, natoms) = nom_i64(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, nder) = nom_i64(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, charge) = nom_i8(input)?;
+    let (input, _) = multispace1(input)?;
+    let (input, spin) = nom_i8(input)?;
+    Ok((input, (natoms as usize, nder as usize, charge, spin)))
+}
+
+/// One atom line: atomic number followed by four floats (x, y, z and the
+/// trailing nuclear-charge/ECP field Gaussian always writes).
+fn atom_line(input: &str) -> IResult<&str, (u8, [f64; 3])> {
+    let (input, _) = multispace0(input)?;
+    let (input, z) = nom::character::complete::u8(input)?;
+    let (input, x) = preceded(multispace1, nom_double)(input)?;
+    let (input, y) = preceded(multispace1, nom_double)(input)?;
+    let (input, zc) = preceded(multispace1, nom_double)(input)?;
+    let (input, _unused) = preceded(multispace1, nom_double)(input)?;
+    Ok((input, (z, [x, y, zc])))
+}
+
+/// One trailing MM point-charge line: `x y z q`.
+fn point_charge_line(input: &str) -> IResult<&str, ([f64; 3], f64)> {
+    let (input, _) = multispace0(input)?;
+    let (input, x) = nom_double(input)?;
+    let (input, y) = preceded(multispace1, nom_double)(input)?;
+    let (input, z) = preceded(multispace1, nom_double)(input)?;
+    let (input, q) = preceded(multispace1, nom_double)(input)?;
+    Ok((input, ([x, y, z], q)))
+}
+
+/// Parse a full EIn file: header, `natoms` atom lines, then any number of
+/// trailing MM point-charge lines.
+pub fn ein_file(input: &str) -> IResult<&str, Calculation> {
+    let (input, (natoms, nder, charge, spin)) = header(input)?;
+    let (input, atoms) = many_m_n(natoms, natoms, preceded(multispace1, atom_line))(input)?;
+    let (input, point_charges) = many0(preceded(multispace0, point_charge_line))(input)?;
+
+    let mut z = Vec::with_capacity(natoms);
+    let mut coords = Vec::with_capacity(natoms);
+    for (atom_z, atom_xyz) in atoms {
+        z.push(atom_z);
+        coords.push(atom_xyz);
+    }
+
+    Ok((
+        input,
+        Calculation {
+            natoms,
+            nder,
+            charge,
+            spin,
+            z,
+            coords,
+            point_charges,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ein_file;
+
+    #[test]
+    fn well_formed_ein_with_irregular_spacing() {
+        let input = "2   0 0 1\n\
+                      1  0.0   0.0  0.0   1.0\n\
+                      8 0.0 0.0 1.0 8.0\n\
+                      2.0   0.0   0.0   0.5\n";
+        let (remainder, calc) = ein_file(input).expect("should parse");
+        assert!(remainder.trim().is_empty());
+        assert_eq!(calc.natoms, 2);
+        assert_eq!(calc.nder, 0);
+        assert_eq!(calc.charge, 0);
+        assert_eq!(calc.spin, 1);
+        assert_eq!(calc.z, vec![1, 8]);
+        assert_eq!(calc.coords, vec![[0.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(calc.point_charges, vec![([2.0, 0.0, 0.0], 0.5)]);
+    }
+
+    #[test]
+    fn truncated_atom_block_fails_to_parse() {
+        // Header claims 3 atoms but only 2 are present.
+        let input = "3 0 0 1\n\
+                      1 0.0 0.0 0.0 1.0\n\
+                      8 0.0 0.0 1.0 8.0\n";
+        assert!(ein_file(input).is_err());
+    }
+
+    #[test]
+    fn malformed_point_charge_line_is_left_unconsumed() {
+        // Point charge line is missing its charge field; `ein_file` must not
+        // silently drop it, so the caller can see it in the remainder and
+        // turn it into an error.
+        let input = "2 0 0 1\n\
+                      1 0.0 0.0 0.0 1.0\n\
+                      8 0.0 0.0 1.0 8.0\n\
+                      2.0 0.0 0.0\n";
+        let (remainder, _calc) = ein_file(input).expect("atom block alone should still parse");
+        assert!(!remainder.trim().is_empty());
+    }
+}
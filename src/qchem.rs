@@ -0,0 +1,355 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::{parse_nums_from_str, Calculation, QchemError, QmPackage, Result};
+
+/// Force constants in `hessian.dat` are in Q-Chem's native units; this is the
+/// factor Gaussian's `external` interface expects them converted by.
+const QCHEM_CONV_FACTOR: f64 = 4.46552493159e-4;
+
+/// Q-Chem reports the dipole moment in Debye; Gaussian's `external`
+/// interface wants it in atomic units (e * a0).
+const DEBYE_TO_AU: f64 = 1.0 / 2.541746;
+
+const QCHEM_OUT: &str = "qchem.out";
+const QCHEM_IN: &str = "qchem.in";
+
+/// Q-Chem backend: knows Q-Chem's output file names (`qchem.out`,
+/// `efield.dat`, `hessian.dat`) and line markers.
+pub struct QChem;
+
+fn parse_energy_str(qchem_out: &str) -> Result<f64> {
+    let eline = qchem_out
+        .lines()
+        .filter(|x| x.starts_with(" The QM part of the energy is"))
+        .next()
+        .ok_or_else(|| QchemError::MissingEnergyLine {
+            file: QCHEM_OUT.to_string(),
+        })?;
+
+    eline
+        .strip_prefix(" The QM part of the energy is")
+        .unwrap()
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| QchemError::Parse {
+            file: QCHEM_OUT.to_string(),
+            field: "energy".to_string(),
+        })
+}
+
+fn parse_dipole_str(qchem_out: &str) -> Result<[f64; 3]> {
+    let mut lines = qchem_out.lines();
+    let components_line = lines
+        .find(|x| x.trim_start().starts_with("Dipole Moment (Debye)"))
+        .and_then(|_| lines.next())
+        .ok_or_else(|| QchemError::Parse {
+            file: QCHEM_OUT.to_string(),
+            field: "dipole moment".to_string(),
+        })?;
+
+    let tokens: Vec<&str> = components_line.split_whitespace().collect();
+    // "X  0.0000  Y  0.0000  Z  0.0000"
+    if tokens.len() != 6 {
+        return Err(QchemError::Parse {
+            file: QCHEM_OUT.to_string(),
+            field: "dipole moment".to_string(),
+        });
+    }
+    let mut xyz = [0.0; 3];
+    for (i, idx) in [1, 3, 5].iter().enumerate() {
+        xyz[i] = tokens[*idx]
+            .parse::<f64>()
+            .map_err(|_| QchemError::Parse {
+                file: QCHEM_OUT.to_string(),
+                field: "dipole moment".to_string(),
+            })?
+            * DEBYE_TO_AU;
+    }
+    Ok(xyz)
+}
+
+fn parse_charges_str(qchem_out: &str, natoms: usize) -> Result<Vec<f64>> {
+    let mut lines = qchem_out.lines();
+    lines
+        .find(|x| x.trim() == "Ground-State Mulliken Net Atomic Charges")
+        .ok_or_else(|| QchemError::Parse {
+            file: QCHEM_OUT.to_string(),
+            field: "atomic charges".to_string(),
+        })?;
+    // Skip the blank line, column header and the dashed separator.
+    lines.next();
+    lines.next();
+    lines.next();
+
+    let mut charges = Vec::with_capacity(natoms);
+    for _ in 0..natoms {
+        let line = lines.next().ok_or_else(|| QchemError::CountMismatch {
+            expected: natoms,
+            got: charges.len(),
+            file: QCHEM_OUT.to_string(),
+        })?;
+        let charge = line
+            .split_whitespace()
+            .last()
+            .ok_or_else(|| QchemError::Parse {
+                file: QCHEM_OUT.to_string(),
+                field: "atomic charge".to_string(),
+            })?
+            .parse::<f64>()
+            .map_err(|_| QchemError::Parse {
+                file: QCHEM_OUT.to_string(),
+                field: "atomic charge".to_string(),
+            })?;
+        charges.push(charge);
+    }
+    Ok(charges)
+}
+
+/// `efield.dat` holds the atomic gradient (3 * natoms values) and, whenever
+/// Q-Chem was asked for dipole derivatives, 9 * natoms more values
+/// immediately after it. Parses every float in the file without enforcing an
+/// exact count, since the dipole-derivative tail is optional.
+fn read_efield_floats(rundir: &Path) -> Result<Vec<f64>> {
+    let data = read_to_string(rundir.join("efield.dat"))?;
+    data.split_whitespace()
+        .map(|x| x.parse::<f64>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| QchemError::Parse {
+            file: "efield.dat".to_string(),
+            field: "gradient/dipole derivative".to_string(),
+        })
+}
+
+impl QmPackage for QChem {
+    fn unit_conversion(&self) -> f64 {
+        QCHEM_CONV_FACTOR
+    }
+
+    fn parse_energy(&self, rundir: &Path) -> Result<f64> {
+        parse_energy_str(&read_to_string(rundir.join(QCHEM_OUT))?)
+    }
+
+    fn parse_gradient(&self, rundir: &Path, natoms: usize) -> Result<Vec<[f64; 3]>> {
+        let data = read_efield_floats(rundir)?;
+        let need = 3 * natoms;
+        if data.len() < need {
+            return Err(QchemError::CountMismatch {
+                expected: need,
+                got: data.len(),
+                file: "efield.dat".to_string(),
+            });
+        }
+        Ok(data[..need]
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect())
+    }
+
+    fn parse_dipole_derivative(&self, rundir: &Path, natoms: usize) -> Result<Vec<[f64; 3]>> {
+        let data = read_efield_floats(rundir)?;
+        let grad_len = 3 * natoms;
+        let deriv_len = 9 * natoms;
+        if data.len() < grad_len + deriv_len {
+            // Q-Chem wasn't asked for dipole derivatives this run.
+            return Ok(vec![[0.0, 0.0, 0.0]; 3 * natoms]);
+        }
+        Ok(data[grad_len..grad_len + deriv_len]
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect())
+    }
+
+    fn parse_hessian(&self, rundir: &Path, natoms: usize) -> Result<Vec<f64>> {
+        let natoms: u16 = natoms as u16;
+        let n_hessian = (3 * natoms) * (3 * natoms + 1) / 2;
+        parse_nums_from_str::<f64>(
+            n_hessian,
+            read_to_string(rundir.join("hessian.dat"))?,
+            "hessian.dat",
+            "hessian",
+        )
+    }
+
+    fn parse_dipole(&self, rundir: &Path) -> Result<[f64; 3]> {
+        parse_dipole_str(&read_to_string(rundir.join(QCHEM_OUT))?)
+    }
+
+    fn parse_charges(&self, rundir: &Path, natoms: usize) -> Result<Vec<f64>> {
+        parse_charges_str(&read_to_string(rundir.join(QCHEM_OUT))?, natoms)
+    }
+
+    fn parse_point_charge_gradient(
+        &self,
+        rundir: &Path,
+        npoints: usize,
+    ) -> Result<Vec<[f64; 3]>> {
+        if npoints == 0 {
+            return Ok(Vec::new());
+        }
+        let npoints: u16 = npoints as u16;
+        let mut data = parse_nums_from_str::<f64>(
+            3 * npoints,
+            read_to_string(rundir.join("ptchg_grad.dat"))?,
+            "ptchg_grad.dat",
+            "point charge gradient",
+        )?;
+        let mut gradient = Vec::with_capacity(npoints.into());
+        for _ in 0..npoints {
+            let xyz: Vec<f64> = data.drain(..3).collect();
+            gradient.push([xyz[0], xyz[1], xyz[2]]);
+        }
+        Ok(gradient)
+    }
+
+    fn write_input(&self, calc: &Calculation) -> String {
+        let mut input = format!(
+            "$molecule\n{} {}\n{}\n$end\n",
+            calc.charge,
+            calc.spin,
+            calc.get_geometry()
+        );
+        if !calc.point_charges.is_empty() {
+            input.push_str("\n$external_charges\n");
+            for (xyz, q) in &calc.point_charges {
+                input.push_str(&format!("{}   {}   {}   {}\n", xyz[0], xyz[1], xyz[2], q));
+            }
+            input.push_str("$end\n");
+        }
+        input
+    }
+
+    fn input_file_name(&self) -> &'static str {
+        QCHEM_IN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Calculation;
+    use std::fs;
+
+    fn rundir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("qchem_g16_test_{}", name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_dipole_str_reads_debye_components_as_atomic_units() {
+        let out = "Dipole Moment (Debye)\n\
+                      X     1.000000    Y     0.000000    Z    -2.000000\n";
+        let dipole = parse_dipole_str(out).expect("should parse");
+        assert_eq!(dipole, [DEBYE_TO_AU, 0.0, -2.0 * DEBYE_TO_AU]);
+    }
+
+    #[test]
+    fn parse_dipole_str_errors_when_section_missing() {
+        let out = "nothing relevant here\n";
+        assert!(parse_dipole_str(out).is_err());
+    }
+
+    #[test]
+    fn parse_charges_str_reads_natoms_rows_past_the_header() {
+        let out = "Ground-State Mulliken Net Atomic Charges\n\
+                      \n\
+                      Atom                 Charge (a.u.)\n\
+                      ----------------------------------\n\
+                      1 O                    -0.400000\n\
+                      2 H                     0.200000\n\
+                      3 H                     0.200000\n";
+        let charges = parse_charges_str(out, 3).expect("should parse");
+        assert_eq!(charges, vec![-0.4, 0.2, 0.2]);
+    }
+
+    #[test]
+    fn parse_charges_str_errors_when_block_is_short() {
+        let out = "Ground-State Mulliken Net Atomic Charges\n\
+                      \n\
+                      Atom                 Charge (a.u.)\n\
+                      ----------------------------------\n\
+                      1 O                    -0.400000\n";
+        assert!(parse_charges_str(out, 3).is_err());
+    }
+
+    #[test]
+    fn write_input_includes_external_charges_when_present() {
+        let calc = Calculation {
+            natoms: 1,
+            nder: 0,
+            charge: 0,
+            spin: 1,
+            z: vec![8],
+            coords: vec![[0.0, 0.0, 0.0]],
+            point_charges: vec![([1.0, 0.0, 0.0], 0.5)],
+        };
+        let input = QChem.write_input(&calc);
+        assert!(input.contains("$external_charges"));
+        assert!(input.contains("1   0   0   0.5"));
+    }
+
+    #[test]
+    fn write_input_omits_external_charges_block_when_absent() {
+        let calc = Calculation {
+            natoms: 1,
+            nder: 0,
+            charge: 0,
+            spin: 1,
+            z: vec![8],
+            coords: vec![[0.0, 0.0, 0.0]],
+            point_charges: vec![],
+        };
+        let input = QChem.write_input(&calc);
+        assert!(!input.contains("$external_charges"));
+    }
+
+    #[test]
+    fn parse_gradient_reads_first_3n_values_from_efield() {
+        let dir = rundir("gradient");
+        fs::write(
+            dir.join("efield.dat"),
+            "1.0 2.0 3.0 4.0 5.0 6.0",
+        )
+        .unwrap();
+        let gradient = QChem.parse_gradient(&dir, 2).expect("should parse");
+        assert_eq!(gradient, vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    }
+
+    #[test]
+    fn parse_dipole_derivative_falls_back_to_zero_when_not_present() {
+        let dir = rundir("dipole_derivative_absent");
+        fs::write(dir.join("efield.dat"), "1.0 2.0 3.0 4.0 5.0 6.0").unwrap();
+        let deriv = QChem
+            .parse_dipole_derivative(&dir, 2)
+            .expect("should fall back, not error");
+        assert_eq!(deriv, vec![[0.0, 0.0, 0.0]; 6]);
+    }
+
+    #[test]
+    fn parse_dipole_derivative_reads_values_after_the_gradient() {
+        let dir = rundir("dipole_derivative_present");
+        // natoms = 1: 3 gradient values, then 9 dipole-derivative values.
+        fs::write(
+            dir.join("efield.dat"),
+            "1.0 2.0 3.0 \
+             0.1 0.2 0.3 0.4 0.5 0.6 0.7 0.8 0.9",
+        )
+        .unwrap();
+        let deriv = QChem.parse_dipole_derivative(&dir, 1).expect("should parse");
+        assert_eq!(
+            deriv,
+            vec![[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]]
+        );
+    }
+
+    #[test]
+    fn parse_point_charge_gradient_reads_npoints_rows() {
+        let dir = rundir("ptchg_grad");
+        fs::write(dir.join("ptchg_grad.dat"), "1.0 0.0 0.0 0.0 1.0 0.0").unwrap();
+        let gradient = QChem
+            .parse_point_charge_gradient(&dir, 2)
+            .expect("should parse");
+        assert_eq!(gradient, vec![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+    }
+}
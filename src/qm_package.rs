@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use crate::{Calculation, Result};
+
+/// Interface to an external QM program driven through Gaussian's `external`
+/// mechanism. Each backend owns the file names and line markers of its own
+/// output format, so the translation routine can target Q-Chem, ORCA,
+/// NWChem, etc. without caring which one is actually running.
+pub trait QmPackage {
+    /// Factor converting this package's native units into the atomic units
+    /// Gaussian's `external` interface expects (replaces the old global
+    /// `CONV_FACTOR`).
+    fn unit_conversion(&self) -> f64;
+
+    /// Parse the total energy out of this package's output in `rundir`.
+    fn parse_energy(&self, rundir: &Path) -> Result<f64>;
+
+    /// Parse the `natoms`-atom Cartesian gradient out of `rundir`.
+    fn parse_gradient(&self, rundir: &Path, natoms: usize) -> Result<Vec<[f64; 3]>>;
+
+    /// Parse the `natoms`-atom dipole derivative tensor (3 rows of x/y/z per
+    /// atom) out of `rundir`, when the backend wrote one; falls back to all
+    /// zeros when the backend didn't compute it.
+    fn parse_dipole_derivative(&self, rundir: &Path, natoms: usize) -> Result<Vec<[f64; 3]>>;
+
+    /// Parse the packed, atom-major upper-triangular Hessian out of `rundir`.
+    fn parse_hessian(&self, rundir: &Path, natoms: usize) -> Result<Vec<f64>>;
+
+    /// Parse the molecular dipole moment (atomic units) out of `rundir`.
+    fn parse_dipole(&self, rundir: &Path) -> Result<[f64; 3]>;
+
+    /// Parse the per-atom population charges (atomic units) out of `rundir`.
+    fn parse_charges(&self, rundir: &Path, natoms: usize) -> Result<Vec<f64>>;
+
+    /// Parse the gradient contribution on the `npoints` external MM point
+    /// charges out of `rundir`, so QM/MM ONIOM optimizations converge.
+    fn parse_point_charge_gradient(&self, rundir: &Path, npoints: usize) -> Result<Vec<[f64; 3]>>;
+
+    /// Render the input file this package should run for `calc`.
+    fn write_input(&self, calc: &Calculation) -> String;
+
+    /// File name this package expects its input under, relative to `rundir`
+    /// (e.g. so `calc.point_charges` actually reaches the QM run instead of
+    /// being parsed and then discarded).
+    fn input_file_name(&self) -> &'static str;
+}